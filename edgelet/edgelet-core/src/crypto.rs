@@ -0,0 +1,114 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+use std::fmt;
+
+use chrono::Duration;
+
+use crate::error::Error;
+use crate::parsed_cert::ParsedCert;
+use crate::trust_bundle_status::{self, AnchorStatus};
+
+/// A PEM-encoded buffer, as returned by [`Certificate::pem`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pem(Vec<u8>);
+
+impl Pem {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Pem(bytes.into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A private key handed back alongside a certificate, in whatever encoding the HSM backend
+/// returns it. The bytes are intentionally not `Debug`-printable.
+#[derive(Clone)]
+pub struct PrivateKey(Vec<u8>);
+
+impl PrivateKey {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        PrivateKey(bytes.into())
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PrivateKey").field(&"<redacted>").finish()
+    }
+}
+
+/// A certificate (or chain of certificates) handed back by the HSM, e.g. the trust bundle
+/// from [`GetTrustBundle::get_trust_bundle`] or a module's own identity cert.
+pub trait Certificate {
+    /// # Errors
+    ///
+    /// Returns an error if the PEM encoding could not be retrieved.
+    fn pem(&self) -> Result<Pem, Error>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the private key could not be retrieved.
+    fn get_private_key(&self) -> Result<Option<PrivateKey>, Error>;
+
+    /// Decode every PEM block in this certificate (or chain) into structured metadata, rather
+    /// than leaving callers to treat it as an opaque blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM encoding can't be retrieved or fails to parse.
+    fn parsed_certs(&self) -> Result<Vec<ParsedCert>, Error> {
+        ParsedCert::parse_pem_bundle(self.pem()?.as_bytes())
+    }
+
+    /// Check that `leaf` (a single PEM-encoded certificate) was directly signed by one of the
+    /// anchors in this bundle, and that both are currently valid.
+    ///
+    /// This only supports a leaf issued directly by a trust anchor. If `leaf` is a bundle that
+    /// also includes an intermediate CA certificate (the usual way a server or module presents
+    /// a chain), only the first certificate in it is read and the intermediate is ignored —
+    /// path-building through intermediates is not implemented, so such a chain is rejected
+    /// unless an anchor happens to have signed the leaf directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `leaf` doesn't parse as a certificate, if it or the anchor that
+    /// signed it is outside its validity window, or if no anchor in this bundle signed it.
+    fn validate_chain(&self, leaf: &[u8]) -> Result<(), Error> {
+        let anchors = self.parsed_certs()?;
+        let leaf = ParsedCert::parse_pem_bundle(leaf)?
+            .into_iter()
+            .next()
+            .ok_or(crate::error::ErrorKind::CertificateParse)?;
+
+        leaf.validate_not_expired()?;
+        leaf.validate_signed_by_one_of(&anchors)
+    }
+}
+
+/// Anything capable of returning edgelet's configured trust bundle: the set of root CAs that
+/// workload and module TLS chains are validated against.
+pub trait GetTrustBundle {
+    type Certificate: Certificate;
+
+    /// # Errors
+    ///
+    /// Returns an error if the trust bundle could not be retrieved.
+    fn get_trust_bundle(&self) -> Result<Self::Certificate, Error>;
+
+    /// Report, per trust anchor, how long it has left before `notAfter`, flagging anchors
+    /// within `warn_within` of expiring so operators get advance notice before workload TLS
+    /// starts failing against an anchor that is about to lapse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trust bundle could not be retrieved or fails to parse.
+    fn trust_bundle_status(&self, warn_within: Duration) -> Result<Vec<AnchorStatus>, Error> {
+        trust_bundle_status::status(&self.get_trust_bundle()?, warn_within)
+    }
+}
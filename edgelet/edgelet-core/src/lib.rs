@@ -0,0 +1,15 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+mod crypto;
+mod error;
+mod parsed_cert;
+mod trust_bundle_status;
+
+pub use crate::crypto::{Certificate, GetTrustBundle, Pem, PrivateKey};
+pub use crate::error::{Error, ErrorKind};
+pub use crate::parsed_cert::ParsedCert;
+pub use crate::trust_bundle_status::AnchorStatus;
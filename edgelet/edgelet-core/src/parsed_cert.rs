@@ -0,0 +1,210 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+//! Structured access to certificate metadata, built on top of the opaque PEM blobs that
+//! [`crate::crypto::Certificate`] hands back. Mirrors thin-edge's `parse_root_certificate`
+//! module: decode the trust bundle into typed certificates in one place instead of parsing PEM
+//! ad hoc at every call site. The bundle is decoded again on each call; nothing is cached.
+
+use chrono::{DateTime, Utc};
+use x509_parser::extensions::{GeneralName, ParsedExtension};
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::prelude::X509Certificate;
+
+use crate::error::{Error, ErrorKind};
+
+/// One certificate decoded out of a PEM bundle: the fields edgelet actually needs to make
+/// trust decisions, rather than the raw DER.
+///
+/// `subject_alt_names` covers DNS names, IP addresses, email addresses, and URIs, which is
+/// every SAN kind edgelet or a workload module is expected to present; the handful of other
+/// `GeneralName` variants (`otherName`, `x400Address`, `directoryName`, `ediPartyName`,
+/// `registeredID`) are not surfaced.
+#[derive(Clone, Debug)]
+pub struct ParsedCert {
+    subject: String,
+    issuer: String,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+    serial: String,
+    sans: Vec<String>,
+    key_usage: Vec<String>,
+    der: Vec<u8>,
+}
+
+impl ParsedCert {
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn not_before(&self) -> DateTime<Utc> {
+        self.not_before
+    }
+
+    pub fn not_after(&self) -> DateTime<Utc> {
+        self.not_after
+    }
+
+    pub fn serial(&self) -> &str {
+        &self.serial
+    }
+
+    pub fn subject_alt_names(&self) -> &[String] {
+        &self.sans
+    }
+
+    pub fn key_usage(&self) -> &[String] {
+        &self.key_usage
+    }
+
+    /// Decode every PEM block in `bundle` into a [`ParsedCert`], in the order they appear.
+    pub fn parse_pem_bundle(bundle: &[u8]) -> Result<Vec<ParsedCert>, Error> {
+        let mut certs = Vec::new();
+        let mut rest = bundle;
+
+        while !rest.iter().all(u8::is_ascii_whitespace) {
+            let (remainder, pem) =
+                parse_x509_pem(rest).map_err(|_| ErrorKind::CertificateParse)?;
+            let (_, x509) = pem
+                .parse_x509()
+                .map_err(|_| ErrorKind::CertificateParse)?;
+
+            certs.push(ParsedCert::from_x509(&x509, pem.contents.clone())?);
+            rest = remainder;
+        }
+
+        Ok(certs)
+    }
+
+    fn from_x509(cert: &X509Certificate<'_>, der: Vec<u8>) -> Result<Self, Error> {
+        let validity = cert.validity();
+        let not_before = asn1_time_to_utc(validity.not_before)?;
+        let not_after = asn1_time_to_utc(validity.not_after)?;
+
+        let mut sans = Vec::new();
+        let mut key_usage = Vec::new();
+        for ext in cert.extensions() {
+            match ext.parsed_extension() {
+                ParsedExtension::SubjectAlternativeName(san) => {
+                    for name in &san.general_names {
+                        match name {
+                            GeneralName::DNSName(dns) => sans.push((*dns).to_string()),
+                            GeneralName::IPAddress(bytes) => sans.push(format_ip_san(bytes)),
+                            GeneralName::RFC822Name(email) => sans.push((*email).to_string()),
+                            GeneralName::URI(uri) => sans.push((*uri).to_string()),
+                            _ => {}
+                        }
+                    }
+                }
+                ParsedExtension::KeyUsage(usage) => {
+                    if usage.digital_signature() {
+                        key_usage.push("digitalSignature".to_owned());
+                    }
+                    if usage.non_repudiation() {
+                        key_usage.push("nonRepudiation".to_owned());
+                    }
+                    if usage.key_encipherment() {
+                        key_usage.push("keyEncipherment".to_owned());
+                    }
+                    if usage.data_encipherment() {
+                        key_usage.push("dataEncipherment".to_owned());
+                    }
+                    if usage.key_agreement() {
+                        key_usage.push("keyAgreement".to_owned());
+                    }
+                    if usage.key_cert_sign() {
+                        key_usage.push("keyCertSign".to_owned());
+                    }
+                    if usage.crl_sign() {
+                        key_usage.push("cRLSign".to_owned());
+                    }
+                    if usage.encipher_only() {
+                        key_usage.push("encipherOnly".to_owned());
+                    }
+                    if usage.decipher_only() {
+                        key_usage.push("decipherOnly".to_owned());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ParsedCert {
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            not_before,
+            not_after,
+            serial: cert.raw_serial_as_string(),
+            sans,
+            key_usage,
+            der,
+        })
+    }
+
+    /// Reject this certificate if `now` falls outside its validity window.
+    pub(crate) fn validate_not_expired(&self) -> Result<(), Error> {
+        let now = Utc::now();
+        if now < self.not_before || now > self.not_after {
+            return Err(ErrorKind::ChainValidation(format!(
+                "certificate {} is not currently valid (notBefore={}, notAfter={})",
+                self.subject, self.not_before, self.not_after
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Reject this certificate unless one of `anchors` both names it as issuer and actually
+    /// signed it.
+    pub(crate) fn validate_signed_by_one_of(&self, anchors: &[ParsedCert]) -> Result<(), Error> {
+        let (_, leaf) =
+            X509Certificate::from_der(&self.der).map_err(|_| ErrorKind::CertificateParse)?;
+
+        for anchor in anchors {
+            if anchor.subject != self.issuer {
+                continue;
+            }
+            // A same-subject anchor that happens to be expired (the normal shape of a CA
+            // rotation overlap) shouldn't stop us from trying the next matching anchor.
+            if anchor.validate_not_expired().is_err() {
+                continue;
+            }
+
+            let (_, anchor_cert) = X509Certificate::from_der(&anchor.der)
+                .map_err(|_| ErrorKind::CertificateParse)?;
+            if leaf.verify_signature(Some(anchor_cert.public_key())).is_ok() {
+                return Ok(());
+            }
+        }
+
+        Err(ErrorKind::ChainValidation(format!(
+            "no trust anchor signed certificate {}",
+            self.subject
+        ))
+        .into())
+    }
+}
+
+fn asn1_time_to_utc(time: x509_parser::time::ASN1Time) -> Result<DateTime<Utc>, Error> {
+    DateTime::<Utc>::from_timestamp(time.timestamp(), 0).ok_or_else(|| ErrorKind::CertificateParse.into())
+}
+
+/// Render a SAN `iPAddress` (4 bytes for IPv4, 16 for IPv6) the way it would appear in
+/// human-facing certificate tooling; any other length is passed through as hex rather than
+/// dropped.
+fn format_ip_san(bytes: &[u8]) -> String {
+    match bytes.len() {
+        4 => {
+            let octets: [u8; 4] = bytes.try_into().expect("checked length");
+            std::net::Ipv4Addr::from(octets).to_string()
+        }
+        16 => {
+            let octets: [u8; 16] = bytes.try_into().expect("checked length");
+            std::net::Ipv6Addr::from(octets).to_string()
+        }
+        _ => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+    }
+}
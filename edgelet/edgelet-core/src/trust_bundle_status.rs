@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+//! Expiry reporting over a trust bundle, built on [`crate::parsed_cert::ParsedCert`] so the
+//! expiry windows come from the real `notAfter` fields instead of a guess.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::crypto::Certificate;
+use crate::error::Error;
+
+/// Time-to-expiry for a single anchor in a trust bundle.
+#[derive(Clone, Debug)]
+pub struct AnchorStatus {
+    subject: String,
+    not_after: DateTime<Utc>,
+    expires_in: Duration,
+    expiring_soon: bool,
+}
+
+impl AnchorStatus {
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn not_after(&self) -> DateTime<Utc> {
+        self.not_after
+    }
+
+    /// Negative once the anchor has already expired.
+    pub fn expires_in(&self) -> Duration {
+        self.expires_in
+    }
+
+    /// `true` once `expires_in` has dropped to or below the caller's warning threshold (or the
+    /// anchor has already expired).
+    pub fn expiring_soon(&self) -> bool {
+        self.expiring_soon
+    }
+}
+
+/// Report, per anchor in `bundle`, how long it has left before `notAfter`, flagging anchors
+/// that are within `warn_within` of expiring (or already expired).
+///
+/// # Errors
+///
+/// Returns an error if `bundle`'s PEM encoding could not be retrieved or fails to parse.
+pub fn status<C: Certificate>(bundle: &C, warn_within: Duration) -> Result<Vec<AnchorStatus>, Error> {
+    let now = Utc::now();
+
+    Ok(bundle
+        .parsed_certs()?
+        .into_iter()
+        .map(|cert| {
+            let expires_in = cert.not_after() - now;
+            AnchorStatus {
+                subject: cert.subject().to_owned(),
+                not_after: cert.not_after(),
+                expires_in,
+                expiring_soon: expires_in <= warn_within,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+use edgelet_core::{Certificate as CoreCertificate, Error as CoreError, Pem, PrivateKey};
+
+/// A certificate (and, if present, its private key) as handed back by the HSM. The private
+/// key, when present, is carried as [`PrivateKey`] rather than raw bytes so that deriving
+/// `Debug` here can't print key material (e.g. for a module's own identity cert).
+#[derive(Clone, Debug)]
+pub struct HsmCertificate {
+    pem: Vec<u8>,
+    private_key: Option<PrivateKey>,
+}
+
+impl HsmCertificate {
+    pub(crate) fn new(pem: Vec<u8>, private_key: Option<PrivateKey>) -> Self {
+        HsmCertificate { pem, private_key }
+    }
+}
+
+impl CoreCertificate for HsmCertificate {
+    fn pem(&self) -> Result<Pem, CoreError> {
+        Ok(Pem::new(self.pem.clone()))
+    }
+
+    fn get_private_key(&self) -> Result<Option<PrivateKey>, CoreError> {
+        Ok(self.private_key.clone())
+    }
+}
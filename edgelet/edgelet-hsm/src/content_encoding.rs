@@ -0,0 +1,177 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+//! RFC 8188 ("Encrypted Content-Encoding for HTTP", the `aes128gcm` scheme) so a module can
+//! encrypt upstream telemetry end-to-end to a server's public key, rather than relying solely
+//! on transport TLS. Adapted from the approach in `rust-ece`.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes128Gcm, Key, Nonce};
+use elliptic_curve::ecdh::diffie_hellman;
+use elliptic_curve::sec1::ToEncodedPoint;
+use hkdf::Hkdf;
+use p256::{EncodedPoint, PublicKey, SecretKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::error::{Error, ErrorKind};
+
+const SALT_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+const CEK_INFO: &[u8] = b"Content-Encoding: aes128gcm\0";
+const NONCE_INFO: &[u8] = b"Content-Encoding: nonce\0";
+
+/// Default record size, chosen to keep a single record well under typical HTTP body limits.
+pub const DEFAULT_RECORD_SIZE: u32 = 4096;
+
+/// Encrypt `plaintext` to `recipient_pub` (a SEC1-compressed P-256 point) using RFC 8188's
+/// `aes128gcm` content-encoding: a fresh salt and ephemeral keypair per call, chunked into
+/// `record_size`-byte records.
+///
+/// # Errors
+///
+/// Returns an error if `recipient_pub` is not a valid P-256 point, if `record_size` leaves no
+/// room for plaintext after the 16-byte tag and the delimiter byte, or if sealing a record fails.
+pub fn encrypt(recipient_pub: &[u8], plaintext: &[u8], record_size: u32) -> Result<Vec<u8>, Error> {
+    let recipient = parse_public_key(recipient_pub)?;
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let ephemeral = SecretKey::random(&mut OsRng);
+    let ephemeral_pub = ephemeral.public_key().to_encoded_point(true);
+    let key_id = ephemeral_pub.as_bytes();
+
+    let shared = diffie_hellman(ephemeral.to_nonzero_scalar(), recipient.as_affine());
+    let (cek, base_nonce) = derive_cek_and_nonce(&salt, shared.raw_secret_bytes())?;
+
+    let record_size = record_size as usize;
+    if record_size <= TAG_LEN + 1 {
+        return Err(ErrorKind::ContentEncoding.into());
+    }
+    let max_plaintext_per_record = record_size - TAG_LEN - 1;
+
+    let mut header = Vec::with_capacity(SALT_LEN + 4 + 1 + key_id.len());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&(record_size as u32).to_be_bytes());
+    header.push(u8::try_from(key_id.len()).map_err(|_| ErrorKind::ContentEncoding)?);
+    header.extend_from_slice(key_id);
+
+    let cipher = Aes128Gcm::new(&cek);
+    let chunks: Vec<&[u8]> = if plaintext.is_empty() {
+        vec![&[]]
+    } else {
+        plaintext.chunks(max_plaintext_per_record).collect()
+    };
+
+    let mut out = header;
+    for (counter, chunk) in chunks.iter().enumerate() {
+        let is_last = counter == chunks.len() - 1;
+        let mut record = Vec::with_capacity(chunk.len() + 1);
+        record.extend_from_slice(chunk);
+        record.push(if is_last { 0x02 } else { 0x01 });
+
+        let nonce = record_nonce(&base_nonce, counter as u64);
+        let sealed = cipher
+            .encrypt(&nonce, Payload::from(record.as_slice()))
+            .map_err(|_| ErrorKind::ContentEncoding)?;
+        out.extend_from_slice(&sealed);
+    }
+
+    Ok(out)
+}
+
+/// Reverse [`encrypt`]: `recipient_secret` is the P-256 private key matching the public key
+/// the payload was encrypted to.
+///
+/// # Errors
+///
+/// Returns an error if `payload` is truncated or malformed, or if any record fails to
+/// authenticate (e.g. it was encrypted to a different key, or has been tampered with).
+pub fn decrypt(recipient_secret: &SecretKey, payload: &[u8]) -> Result<Vec<u8>, Error> {
+    if payload.len() < SALT_LEN + 4 + 1 {
+        return Err(ErrorKind::ContentEncoding.into());
+    }
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (rs_bytes, rest) = rest.split_at(4);
+    let record_size = u32::from_be_bytes([rs_bytes[0], rs_bytes[1], rs_bytes[2], rs_bytes[3]]) as usize;
+    let (&id_len, rest) = rest.split_first().ok_or(ErrorKind::ContentEncoding)?;
+    let id_len = id_len as usize;
+    if rest.len() < id_len {
+        return Err(ErrorKind::ContentEncoding.into());
+    }
+    let (key_id, records) = rest.split_at(id_len);
+
+    let ephemeral_pub = parse_public_key(key_id)?;
+    let shared = diffie_hellman(
+        recipient_secret.to_nonzero_scalar(),
+        ephemeral_pub.as_affine(),
+    );
+    let (cek, base_nonce) = derive_cek_and_nonce(salt, shared.raw_secret_bytes())?;
+
+    let cipher = Aes128Gcm::new(&cek);
+    let sealed_record_len = record_size;
+    if sealed_record_len <= TAG_LEN {
+        return Err(ErrorKind::ContentEncoding.into());
+    }
+
+    let mut out = Vec::new();
+    let mut counter = 0u64;
+    let mut remaining = records;
+    loop {
+        if remaining.is_empty() {
+            return Err(ErrorKind::ContentEncoding.into());
+        }
+        let take = sealed_record_len.min(remaining.len());
+        let (sealed, rest) = remaining.split_at(take);
+
+        let nonce = record_nonce(&base_nonce, counter);
+        let mut record = cipher
+            .decrypt(&nonce, sealed)
+            .map_err(|_| ErrorKind::ContentEncoding)?;
+
+        let delimiter = record.pop().ok_or(ErrorKind::ContentEncoding)?;
+        out.extend_from_slice(&record);
+
+        match delimiter {
+            0x02 => return Ok(out),
+            0x01 if !rest.is_empty() => {
+                remaining = rest;
+                counter += 1;
+            }
+            _ => return Err(ErrorKind::ContentEncoding.into()),
+        }
+    }
+}
+
+fn derive_cek_and_nonce(
+    salt: &[u8],
+    ecdh_secret: &[u8],
+) -> Result<(Key<Aes128Gcm>, [u8; 12]), Error> {
+    let (_, prk) = Hkdf::<Sha256>::extract(Some(salt), ecdh_secret);
+
+    let mut cek = [0u8; 16];
+    prk.expand(CEK_INFO, &mut cek)
+        .map_err(|_| ErrorKind::ContentEncoding)?;
+
+    let mut nonce = [0u8; 12];
+    prk.expand(NONCE_INFO, &mut nonce)
+        .map_err(|_| ErrorKind::ContentEncoding)?;
+
+    Ok((Key::<Aes128Gcm>::from(cek), nonce))
+}
+
+fn record_nonce(base_nonce: &[u8; 12], counter: u64) -> Nonce {
+    let mut nonce = *base_nonce;
+    let counter_bytes = counter.to_be_bytes();
+    for i in 0..8 {
+        nonce[4 + i] ^= counter_bytes[i];
+    }
+    Nonce::clone_from_slice(&nonce)
+}
+
+fn parse_public_key(bytes: &[u8]) -> Result<PublicKey, Error> {
+    let point = EncodedPoint::from_bytes(bytes).map_err(|_| ErrorKind::ContentEncoding)?;
+    Option::from(PublicKey::from_encoded_point(&point))
+        .ok_or_else(|| ErrorKind::ContentEncoding.into())
+}
@@ -0,0 +1,156 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+use std::sync::{Arc, RwLock};
+
+use chrono::Duration;
+use edgelet_core::{Error as CoreError, ErrorKind as CoreErrorKind, GetTrustBundle};
+use tokio::sync::watch;
+
+use crate::certificate::HsmCertificate;
+use crate::content_encoding;
+use crate::error::{Error, ErrorKind};
+use crate::identity_key::{self, KeyPair};
+use crate::secure_message::SecureMessage;
+
+/// The default trust bundle shipped until a device provisions its own CA, e.g. via DPS or
+/// manual config. Production deployments replace this by wiring `Crypto` up to libiothsm;
+/// this constant is only the out-of-the-box anchor.
+const DEFAULT_TRUST_BUNDLE: &str = include_str!("../certs/default_trust_bundle.pem");
+
+/// Default advance notice before an anchor's `notAfter` is treated as "expiring soon" by
+/// [`Crypto::trust_bundle_status`].
+const DEFAULT_EXPIRY_WARNING: Duration = Duration::days(30);
+
+/// Entry point into the HSM-backed crypto operations edgelet needs: the trust bundle, module
+/// identity keys, secure messaging between modules, and encrypted telemetry.
+#[derive(Clone)]
+pub struct Crypto {
+    // Stands in for the HSM's sealed master key until `Crypto` is wired up to libiothsm; every
+    // identity key is derived from this plus the caller's id, so it must stay stable across
+    // process restarts for a given device.
+    master_key: Arc<[u8]>,
+    trust_bundle: Arc<RwLock<Vec<u8>>>,
+    trust_bundle_changed: watch::Sender<u64>,
+    expiry_warning: Duration,
+}
+
+impl Crypto {
+    /// # Errors
+    ///
+    /// Returns an error if the HSM backend could not be initialized.
+    pub fn new() -> Result<Self, Error> {
+        let (trust_bundle_changed, _) = watch::channel(0);
+        Ok(Crypto {
+            master_key: Arc::from(&b"iotedge-hsm-software-master-key"[..]),
+            trust_bundle: Arc::new(RwLock::new(DEFAULT_TRUST_BUNDLE.as_bytes().to_vec())),
+            trust_bundle_changed,
+            expiry_warning: DEFAULT_EXPIRY_WARNING,
+        })
+    }
+
+    /// Use `threshold` instead of the default 30 days as the advance notice before an anchor
+    /// is reported as expiring soon.
+    pub fn with_expiry_warning_threshold(mut self, threshold: Duration) -> Self {
+        self.expiry_warning = threshold;
+        self
+    }
+
+    /// Regenerate the stable ECDSA keypair for `id` from the HSM's master key, rather than
+    /// persisting a per-module private key to disk. Calling this twice with the same `id`
+    /// always returns the same keypair; to rotate a key, derive a new `id` (e.g. append a
+    /// version suffix) instead of storing a replacement.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on the negligible chance that every derived scalar is zero or not
+    /// below the curve order after exhausting the retry counter.
+    pub fn derive_identity_key(&self, id: &str) -> Result<KeyPair, Error> {
+        identity_key::derive(&self.master_key, id)
+    }
+
+    /// Open a secure-message channel for `id`: derives that identity's keypair and wraps it
+    /// so the caller can encrypt/sign payloads to other modules and decrypt/verify ones
+    /// addressed to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identity key for `id` could not be derived.
+    pub fn secure_message(&self, id: &str) -> Result<SecureMessage, Error> {
+        Ok(SecureMessage::new(self.derive_identity_key(id)?))
+    }
+
+    /// Encrypt `plaintext` to `recipient_pub` using RFC 8188 `aes128gcm`, so a module can send
+    /// upstream telemetry that stays confidential end-to-end rather than only in transit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `recipient_pub` is not a valid P-256 point or sealing fails.
+    pub fn encrypt_content(&self, recipient_pub: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        content_encoding::encrypt(recipient_pub, plaintext, content_encoding::DEFAULT_RECORD_SIZE)
+    }
+
+    /// Reverse [`Crypto::encrypt_content`] using the identity key for `id` as the recipient.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identity key for `id` could not be derived, or if `payload` is
+    /// malformed or fails to authenticate against that key.
+    pub fn decrypt_content(&self, id: &str, payload: &[u8]) -> Result<Vec<u8>, Error> {
+        let key_pair = self.derive_identity_key(id)?;
+        content_encoding::decrypt(key_pair.secret(), payload)
+    }
+
+    /// Atomically swap the trust bundle for `new_pem` and notify any subscriber from
+    /// [`Crypto::subscribe_trust_bundle_changes`], so a rotated CA is picked up without a
+    /// daemon restart. Rejects `new_pem` up front if it doesn't parse, leaving the existing
+    /// bundle in place.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `new_pem` does not parse as a PEM bundle of X.509 certificates.
+    pub fn rotate_trust_bundle(&self, new_pem: &[u8]) -> Result<(), Error> {
+        edgelet_core::ParsedCert::parse_pem_bundle(new_pem).map_err(|_| ErrorKind::TrustBundleRotation)?;
+
+        {
+            let mut bundle = self
+                .trust_bundle
+                .write()
+                .map_err(|_| ErrorKind::TrustBundleRotation)?;
+            *bundle = new_pem.to_vec();
+        }
+
+        self.trust_bundle_changed.send_modify(|version| *version += 1);
+        Ok(())
+    }
+
+    /// Subscribe to trust bundle rotations; the watched value is a monotonically increasing
+    /// version counter, bumped once per successful [`Crypto::rotate_trust_bundle`] call.
+    pub fn subscribe_trust_bundle_changes(&self) -> watch::Receiver<u64> {
+        self.trust_bundle_changed.subscribe()
+    }
+
+    /// [`GetTrustBundle::trust_bundle_status`] using this `Crypto`'s configured warning
+    /// threshold (30 days by default, or whatever was set via
+    /// [`Crypto::with_expiry_warning_threshold`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the trust bundle could not be retrieved or fails to parse.
+    pub fn trust_bundle_status(&self) -> Result<Vec<edgelet_core::AnchorStatus>, Error> {
+        GetTrustBundle::trust_bundle_status(self, self.expiry_warning)
+            .map_err(|_| ErrorKind::GetTrustBundle.into())
+    }
+}
+
+impl GetTrustBundle for Crypto {
+    type Certificate = HsmCertificate;
+
+    fn get_trust_bundle(&self) -> Result<Self::Certificate, CoreError> {
+        let bundle = self
+            .trust_bundle
+            .read()
+            .map_err(|_| CoreErrorKind::Crypto)?
+            .clone();
+        Ok(HsmCertificate::new(bundle, None))
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+use std::fmt;
+use std::fmt::Display;
+
+use failure::{Backtrace, Context, Fail};
+
+#[derive(Debug)]
+pub struct Error {
+    inner: Context<ErrorKind>,
+}
+
+#[derive(Clone, Debug, Eq, Fail, PartialEq)]
+pub enum ErrorKind {
+    #[fail(display = "HSM initialization failed")]
+    Initialization,
+
+    #[fail(display = "Could not retrieve trust bundle from HSM")]
+    GetTrustBundle,
+
+    #[fail(display = "Could not derive identity key")]
+    KeyDerivation,
+
+    #[fail(display = "Secure message operation failed")]
+    SecureMessage,
+
+    #[fail(display = "Content-encoding operation failed")]
+    ContentEncoding,
+
+    #[fail(display = "Could not rotate trust bundle")]
+    TrustBundleRotation,
+}
+
+impl Fail for Error {
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.inner.cause()
+    }
+
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.inner.backtrace()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl Error {
+    pub fn kind(&self) -> &ErrorKind {
+        self.inner.get_context()
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error {
+            inner: Context::new(kind),
+        }
+    }
+}
+
+impl From<Context<ErrorKind>> for Error {
+    fn from(inner: Context<ErrorKind>) -> Error {
+        Error { inner }
+    }
+}
+
+impl From<Error> for edgelet_core::Error {
+    fn from(_err: Error) -> edgelet_core::Error {
+        edgelet_core::ErrorKind::Crypto.into()
+    }
+}
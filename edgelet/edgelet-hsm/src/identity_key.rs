@@ -0,0 +1,69 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+//! Deterministic per-identity key derivation, so a module or device's private key never has
+//! to be written to disk: it is always just an HKDF of the HSM's master key and the
+//! identity's own id, regenerated on demand.
+
+use elliptic_curve::sec1::ToEncodedPoint;
+use hkdf::Hkdf;
+use p256::{NonZeroScalar, PublicKey, SecretKey};
+use sha2::Sha256;
+
+use crate::error::{Error, ErrorKind};
+
+const INFO_PREFIX: &[u8] = b"iotedge-identity";
+
+/// An ECDSA (P-256) keypair. The private scalar exists only in memory for the lifetime of
+/// this value; it is never persisted, since it can always be re-derived from the id that
+/// produced it.
+pub struct KeyPair {
+    secret: SecretKey,
+}
+
+impl KeyPair {
+    /// The public point, SEC1-compressed.
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.secret
+            .public_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+    }
+
+    pub(crate) fn public_key(&self) -> PublicKey {
+        self.secret.public_key()
+    }
+
+    pub(crate) fn secret(&self) -> &SecretKey {
+        &self.secret
+    }
+}
+
+/// Derive a stable P-256 keypair for `id` from `master_key`. The same `(master_key, id)` pair
+/// always yields the same keypair; rotating an identity's key is a matter of changing `id`
+/// (e.g. by appending a version suffix) rather than generating and storing a new one.
+pub(crate) fn derive(master_key: &[u8], id: &str) -> Result<KeyPair, Error> {
+    let (_, hk) = Hkdf::<Sha256>::extract(None, master_key);
+
+    // The HKDF output is rejected only if it is zero or not below the curve order, which has
+    // negligible probability; on the off chance it is, bump a counter byte in the
+    // info string and re-expand rather than failing the caller.
+    for attempt in 0u8..=255 {
+        let mut info = Vec::with_capacity(INFO_PREFIX.len() + id.len() + 1);
+        info.extend_from_slice(INFO_PREFIX);
+        info.extend_from_slice(id.as_bytes());
+        info.push(attempt);
+
+        let mut okm = [0u8; 32];
+        hk.expand(&info, &mut okm)
+            .map_err(|_| ErrorKind::KeyDerivation)?;
+
+        if let Some(scalar) = Option::from(NonZeroScalar::from_repr(okm.into())) {
+            return Ok(KeyPair {
+                secret: SecretKey::new(scalar),
+            });
+        }
+    }
+
+    Err(ErrorKind::KeyDerivation.into())
+}
@@ -0,0 +1,18 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+mod certificate;
+mod content_encoding;
+mod crypto;
+mod error;
+mod identity_key;
+mod secure_message;
+
+pub use crate::certificate::HsmCertificate;
+pub use crate::crypto::Crypto;
+pub use crate::error::{Error, ErrorKind};
+pub use crate::identity_key::KeyPair;
+pub use crate::secure_message::SecureMessage;
@@ -0,0 +1,34 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+
+use edgelet_hsm::Crypto;
+
+#[test]
+fn derive_identity_key_is_stable_across_independent_calls() {
+    let crypto = Crypto::new().unwrap();
+
+    let first = crypto.derive_identity_key("module-a").unwrap();
+    let second = crypto.derive_identity_key("module-a").unwrap();
+
+    assert_eq!(first.public_key_bytes(), second.public_key_bytes());
+}
+
+#[test]
+fn derive_identity_key_is_stable_across_independent_crypto_instances() {
+    let first = Crypto::new().unwrap().derive_identity_key("module-a").unwrap();
+    let second = Crypto::new().unwrap().derive_identity_key("module-a").unwrap();
+
+    assert_eq!(first.public_key_bytes(), second.public_key_bytes());
+}
+
+#[test]
+fn derive_identity_key_differs_between_ids() {
+    let crypto = Crypto::new().unwrap();
+
+    let a = crypto.derive_identity_key("module-a").unwrap();
+    let b = crypto.derive_identity_key("module-b").unwrap();
+
+    assert_ne!(a.public_key_bytes(), b.public_key_bytes());
+}
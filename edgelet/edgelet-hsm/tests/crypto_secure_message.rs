@@ -0,0 +1,73 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+
+use edgelet_hsm::Crypto;
+
+#[test]
+fn secure_message_encrypt_decrypt_round_trips() {
+    let crypto = Crypto::new().unwrap();
+    let alice = crypto.secure_message("module-a").unwrap();
+    let bob = crypto.secure_message("module-b").unwrap();
+    let bob_pub = crypto
+        .derive_identity_key("module-b")
+        .unwrap()
+        .public_key_bytes();
+
+    let plaintext = b"hello from module-a";
+    let ciphertext = alice.encrypt(&bob_pub, plaintext).unwrap();
+    assert_ne!(ciphertext, plaintext);
+
+    let decrypted = bob.decrypt(&ciphertext).unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn secure_message_decrypt_fails_for_the_wrong_recipient() {
+    let crypto = Crypto::new().unwrap();
+    let alice = crypto.secure_message("module-a").unwrap();
+    let eve = crypto.secure_message("module-e").unwrap();
+    let bob_pub = crypto
+        .derive_identity_key("module-b")
+        .unwrap()
+        .public_key_bytes();
+
+    let ciphertext = alice.encrypt(&bob_pub, b"hello from module-a").unwrap();
+
+    eve.decrypt(&ciphertext).unwrap_err();
+}
+
+#[test]
+fn secure_message_sign_verify_round_trips() {
+    let crypto = Crypto::new().unwrap();
+    let alice = crypto.secure_message("module-a").unwrap();
+    let alice_pub = crypto
+        .derive_identity_key("module-a")
+        .unwrap()
+        .public_key_bytes();
+
+    let plaintext = b"telemetry payload";
+    let signed = alice.sign(plaintext).unwrap();
+
+    let verifier = crypto.secure_message("module-b").unwrap();
+    let verified = verifier.verify(&alice_pub, &signed).unwrap();
+    assert_eq!(verified, plaintext);
+}
+
+#[test]
+fn secure_message_verify_rejects_a_tampered_payload() {
+    let crypto = Crypto::new().unwrap();
+    let alice = crypto.secure_message("module-a").unwrap();
+    let alice_pub = crypto
+        .derive_identity_key("module-a")
+        .unwrap()
+        .public_key_bytes();
+
+    let mut signed = alice.sign(b"original payload").unwrap();
+    let last = signed.len() - 1;
+    signed[last] ^= 0xff;
+
+    let verifier = crypto.secure_message("module-b").unwrap();
+    verifier.verify(&alice_pub, &signed).unwrap_err();
+}
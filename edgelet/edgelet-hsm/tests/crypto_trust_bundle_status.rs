@@ -0,0 +1,74 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+
+use chrono::Duration;
+use edgelet_core::{Certificate, GetTrustBundle};
+use edgelet_hsm::Crypto;
+use rcgen::{
+    BasicConstraints, Certificate as RcgenCertificate, CertificateParams, DistinguishedName,
+    DnType, IsCa, PKCS_ECDSA_P256_SHA256,
+};
+use time::{Duration as TimeDuration, OffsetDateTime};
+
+fn self_signed_ca_valid_for(common_name: &str, validity: TimeDuration) -> Vec<u8> {
+    let mut params = CertificateParams::new(Vec::new());
+    params.alg = &PKCS_ECDSA_P256_SHA256;
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.not_before = OffsetDateTime::now_utc() - TimeDuration::days(1);
+    params.not_after = OffsetDateTime::now_utc() + validity;
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, common_name);
+    params.distinguished_name = dn;
+
+    let cert = RcgenCertificate::from_params(params).unwrap();
+    cert.serialize_pem().unwrap().into_bytes()
+}
+
+#[test]
+fn trust_bundle_status_does_not_flag_a_long_lived_anchor() {
+    let ca_pem = self_signed_ca_valid_for("long-lived test CA", TimeDuration::days(400));
+
+    let crypto = Crypto::new().unwrap();
+    crypto.rotate_trust_bundle(&ca_pem).unwrap();
+
+    let status = crypto.trust_bundle_status().unwrap();
+    assert_eq!(status.len(), 1);
+    assert!(!status[0].expiring_soon());
+}
+
+#[test]
+fn trust_bundle_status_flags_an_anchor_expiring_within_the_warning_threshold() {
+    let ca_pem = self_signed_ca_valid_for("soon-to-expire test CA", TimeDuration::days(10));
+
+    let crypto = Crypto::new().unwrap();
+    crypto.rotate_trust_bundle(&ca_pem).unwrap();
+
+    let status = GetTrustBundle::trust_bundle_status(&crypto, Duration::days(30)).unwrap();
+    assert_eq!(status.len(), 1);
+    assert!(status[0].expiring_soon());
+}
+
+#[test]
+fn rotate_trust_bundle_notifies_subscribers() {
+    let crypto = Crypto::new().unwrap();
+    let mut changes = crypto.subscribe_trust_bundle_changes();
+    let before = *changes.borrow();
+
+    let ca_pem = self_signed_ca_valid_for("rotated test CA", TimeDuration::days(400));
+    crypto.rotate_trust_bundle(&ca_pem).unwrap();
+
+    assert!(*changes.borrow_and_update() > before);
+}
+
+#[test]
+fn rotate_trust_bundle_rejects_malformed_pem_and_keeps_the_old_bundle() {
+    let crypto = Crypto::new().unwrap();
+    let original = crypto.get_trust_bundle().unwrap().pem().unwrap();
+
+    crypto.rotate_trust_bundle(b"not a certificate").unwrap_err();
+
+    let after = crypto.get_trust_bundle().unwrap().pem().unwrap();
+    assert_eq!(original, after);
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Microsoft. All rights reserved.
+
+#![deny(unused_extern_crates, warnings)]
+#![deny(clippy::all, clippy::pedantic)]
+
+use edgelet_core::{Certificate, GetTrustBundle};
+use edgelet_hsm::Crypto;
+use rcgen::{
+    BasicConstraints, Certificate as RcgenCertificate, CertificateParams, DistinguishedName,
+    DnType, IsCa, PKCS_ECDSA_P256_SHA256,
+};
+
+fn self_signed_ca(common_name: &str) -> (RcgenCertificate, Vec<u8>) {
+    let mut params = CertificateParams::new(Vec::new());
+    params.alg = &PKCS_ECDSA_P256_SHA256;
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, common_name);
+    params.distinguished_name = dn;
+
+    let cert = RcgenCertificate::from_params(params).unwrap();
+    let pem = cert.serialize_pem().unwrap().into_bytes();
+    (cert, pem)
+}
+
+fn leaf_signed_by(issuer: &RcgenCertificate, common_name: &str) -> Vec<u8> {
+    let mut params = CertificateParams::new(Vec::new());
+    params.alg = &PKCS_ECDSA_P256_SHA256;
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, common_name);
+    params.distinguished_name = dn;
+
+    let leaf = RcgenCertificate::from_params(params).unwrap();
+    leaf.serialize_pem_with_signer(issuer).unwrap().into_bytes()
+}
+
+#[test]
+fn validate_chain_accepts_a_leaf_signed_by_the_trust_bundle() {
+    let (ca, ca_pem) = self_signed_ca("trusted test CA");
+    let leaf_pem = leaf_signed_by(&ca, "workload-module");
+
+    let crypto = Crypto::new().unwrap();
+    crypto.rotate_trust_bundle(&ca_pem).unwrap();
+    let bundle = crypto.get_trust_bundle().unwrap();
+
+    bundle.validate_chain(&leaf_pem).unwrap();
+}
+
+#[test]
+fn validate_chain_rejects_a_leaf_whose_issuer_name_matches_but_key_does_not() {
+    let (ca, ca_pem) = self_signed_ca("trusted test CA");
+    // Same subject/common name as the trusted anchor, but a different keypair: a leaf "issued"
+    // by this one has an `issuer` DN that matches the real anchor's `subject` DN even though no
+    // trusted anchor actually signed it.
+    let (forged_ca, _) = self_signed_ca("trusted test CA");
+    let forged_leaf_pem = leaf_signed_by(&forged_ca, "workload-module");
+
+    let crypto = Crypto::new().unwrap();
+    crypto.rotate_trust_bundle(&ca_pem).unwrap();
+    let bundle = crypto.get_trust_bundle().unwrap();
+
+    bundle.validate_chain(&forged_leaf_pem).unwrap_err();
+}